@@ -3,7 +3,7 @@ use amethyst::{
     core::transform::{Transform, TransformBundle},
     ecs::prelude::{
         Component, DenseVecStorage, Entities, Entity, Join, Read, ReadExpect, ReadStorage,
-        System, World, WriteStorage,
+        System, World, Write, WriteStorage,
     },
     input::{InputBundle, InputHandler, StringBindings},
     prelude::*,
@@ -17,15 +17,29 @@ use amethyst::{
         ImageFormat,
         sprite::SpriteRender, sprite::SpriteSheet, SpriteSheetFormat, Texture,
     },
-    ui::{Anchor, Interactable, RenderUi, Selectable, Selected, UiBundle, UiTransform},
+    ui::{Anchor, Interactable, RenderUi, Selectable, Selected, UiBundle, UiImage, UiTransform},
     utils::application_root_dir,
     window::ScreenDimensions,
 };
 use log::info;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+mod scene_script;
+
+use scene_script::ScriptedScene;
+
+/// Movement speed, in world units per frame, for objects spawned by
+/// left-click; objects from the scene script always carry their own `speed`.
+const DEFAULT_OBJECT_SPEED: f32 = 5.0;
 
 #[derive(Debug)]
 struct SomeObject {
     ordered_to: Option<(f32, f32)>,
+    /// Remaining A*-planned waypoints (world coordinates) leading to `ordered_to`.
+    path: Vec<(f32, f32)>,
+    /// Movement speed, in world units per frame.
+    speed: f32,
 }
 
 impl Component for SomeObject {
@@ -34,7 +48,245 @@ impl Component for SomeObject {
 
 impl SomeObject {
     fn new() -> SomeObject {
-        SomeObject { ordered_to: None }
+        SomeObject::with_speed(DEFAULT_OBJECT_SPEED)
+    }
+
+    fn with_speed(speed: f32) -> SomeObject {
+        SomeObject {
+            ordered_to: None,
+            path: Vec::new(),
+            speed,
+        }
+    }
+}
+
+/// Size, in world units, of a single `NavGrid` cell.
+const NAV_CELL_SIZE: f32 = 16.0;
+
+/// A quantized navigation grid used to A*-path `SomeObject` movement orders
+/// around blocked cells instead of walking straight through them.
+struct NavGrid {
+    width: i32,
+    height: i32,
+    origin: (f32, f32),
+    blocked: Vec<bool>,
+}
+
+impl NavGrid {
+    fn new(width: i32, height: i32, origin: (f32, f32)) -> NavGrid {
+        NavGrid {
+            width,
+            height,
+            origin,
+            blocked: vec![false; (width * height) as usize],
+        }
+    }
+
+    fn cell_of(&self, pos: (f32, f32)) -> (i32, i32) {
+        (
+            ((pos.0 - self.origin.0) / NAV_CELL_SIZE).floor() as i32,
+            ((pos.1 - self.origin.1) / NAV_CELL_SIZE).floor() as i32,
+        )
+    }
+
+    fn world_of(&self, cell: (i32, i32)) -> (f32, f32) {
+        (
+            self.origin.0 + (cell.0 as f32 + 0.5) * NAV_CELL_SIZE,
+            self.origin.1 + (cell.1 as f32 + 0.5) * NAV_CELL_SIZE,
+        )
+    }
+
+    fn in_bounds(&self, cell: (i32, i32)) -> bool {
+        cell.0 >= 0 && cell.0 < self.width && cell.1 >= 0 && cell.1 < self.height
+    }
+
+    fn index(&self, cell: (i32, i32)) -> usize {
+        (cell.1 * self.width + cell.0) as usize
+    }
+
+    fn is_blocked(&self, cell: (i32, i32)) -> bool {
+        !self.in_bounds(cell) || self.blocked[self.index(cell)]
+    }
+
+    #[allow(dead_code)]
+    fn set_blocked(&mut self, cell: (i32, i32)) {
+        if self.in_bounds(cell) {
+            let idx = self.index(cell);
+            self.blocked[idx] = true;
+        }
+    }
+
+    fn neighbors(&self, cell: (i32, i32)) -> Vec<(i32, i32)> {
+        let mut result = Vec::with_capacity(8);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let neighbor = (cell.0 + dx, cell.1 + dy);
+                if self.is_blocked(neighbor) {
+                    continue;
+                }
+                // Disallow cutting corners between two blocked orthogonal neighbors
+                if dx != 0 && dy != 0
+                    && (self.is_blocked((cell.0 + dx, cell.1)) || self.is_blocked((cell.0, cell.1 + dy)))
+                {
+                    continue;
+                }
+                result.push(neighbor);
+            }
+        }
+        result
+    }
+
+    /// Finds a path from `start` to `goal` (world coordinates) with A*,
+    /// returning the cell-center waypoints to follow, excluding `start`.
+    fn find_path(&self, start: (f32, f32), goal: (f32, f32)) -> Option<Vec<(f32, f32)>> {
+        let start_cell = self.cell_of(start);
+        let goal_cell = self.cell_of(goal);
+        if self.is_blocked(goal_cell) {
+            return None;
+        }
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut g_score: HashMap<(i32, i32), f32> = HashMap::new();
+
+        g_score.insert(start_cell, 0.);
+        open.push(OpenEntry {
+            f_score: octile_distance(start_cell, goal_cell),
+            cell: start_cell,
+        });
+
+        while let Some(OpenEntry { cell, .. }) = open.pop() {
+            if cell == goal_cell {
+                return Some(reconstruct_path(&came_from, cell, self));
+            }
+
+            let current_g = g_score[&cell];
+            for neighbor in self.neighbors(cell) {
+                let step_cost = if neighbor.0 != cell.0 && neighbor.1 != cell.1 {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.
+                };
+                let tentative_g = current_g + step_cost;
+                if tentative_g < *g_score.get(&neighbor).unwrap_or(&f32::INFINITY) {
+                    came_from.insert(neighbor, cell);
+                    g_score.insert(neighbor, tentative_g);
+                    open.push(OpenEntry {
+                        f_score: tentative_g + octile_distance(neighbor, goal_cell),
+                        cell: neighbor,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn octile_distance(a: (i32, i32), b: (i32, i32)) -> f32 {
+    let dx = (a.0 - b.0).abs() as f32;
+    let dy = (a.1 - b.1).abs() as f32;
+    dx + dy + (std::f32::consts::SQRT_2 - 2.) * dx.min(dy)
+}
+
+fn reconstruct_path(
+    came_from: &HashMap<(i32, i32), (i32, i32)>,
+    mut current: (i32, i32),
+    grid: &NavGrid,
+) -> Vec<(f32, f32)> {
+    let mut path = vec![grid.world_of(current)];
+    while let Some(&prev) = came_from.get(&current) {
+        current = prev;
+        path.push(grid.world_of(current));
+    }
+    path.pop(); // drop the start cell, the object is already there
+    path.reverse();
+    path
+}
+
+/// Min-heap entry for A*'s open set, ordered by ascending `f_score`.
+struct OpenEntry {
+    f_score: f32,
+    cell: (i32, i32),
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.f_score == other.f_score
+    }
+}
+
+impl Eq for OpenEntry {}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .f_score
+            .partial_cmp(&self.f_score)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod nav_grid_tests {
+    use super::*;
+
+    #[test]
+    fn finds_direct_path_with_no_obstacles() {
+        let grid = NavGrid::new(10, 10, (0., 0.));
+        let goal = (8. + NAV_CELL_SIZE * 3., 8.);
+        let path = grid.find_path((8., 8.), goal).expect("path should be found");
+        assert!(!path.is_empty());
+        assert_eq!(grid.cell_of(*path.last().unwrap()), grid.cell_of(goal));
+    }
+
+    #[test]
+    fn routes_around_a_wall_instead_of_through_it() {
+        let mut grid = NavGrid::new(5, 5, (0., 0.));
+        // Block the whole middle column except the top row, forcing a detour
+        for y in 1..5 {
+            grid.set_blocked((2, y));
+        }
+        let start = (0.5 * NAV_CELL_SIZE, 2.5 * NAV_CELL_SIZE);
+        let goal = (4.5 * NAV_CELL_SIZE, 2.5 * NAV_CELL_SIZE);
+        let path = grid
+            .find_path(start, goal)
+            .expect("path should route around the wall");
+        assert!(path.iter().any(|&p| grid.cell_of(p) == (2, 0)));
+    }
+
+    #[test]
+    fn refuses_to_cut_corners_between_two_blocked_cells() {
+        let mut grid = NavGrid::new(3, 3, (0., 0.));
+        grid.set_blocked((1, 0));
+        grid.set_blocked((0, 1));
+        assert!(!grid.neighbors((0, 0)).contains(&(1, 1)));
+    }
+
+    #[test]
+    fn fails_when_the_goal_cell_is_blocked() {
+        let mut grid = NavGrid::new(3, 3, (0., 0.));
+        grid.set_blocked((2, 2));
+        let start = (0.5 * NAV_CELL_SIZE, 0.5 * NAV_CELL_SIZE);
+        let goal = (2.5 * NAV_CELL_SIZE, 2.5 * NAV_CELL_SIZE);
+        assert!(grid.find_path(start, goal).is_none());
+    }
+
+    #[test]
+    fn octile_distance_matches_formula() {
+        assert_eq!(octile_distance((0, 0), (0, 0)), 0.);
+        let distance = octile_distance((0, 0), (3, 1));
+        let expected = 3. + 1. + (std::f32::consts::SQRT_2 - 2.) * 1.;
+        assert!((distance - expected).abs() < 1e-6);
     }
 }
 
@@ -81,12 +333,13 @@ impl<'s> System<'s> for MarkSelectedSystem {
             mut marked,
         ): Self::SystemData,
     ) {
-        let mut marker_transform = None;
-        for (e, transform, _, _) in (&*entities, &transforms, &selecteds, !&marked).join() {
-            marker_transform = Some((e, transform.clone()));
-        }
+        let newly_selected: Vec<(Entity, Transform)> =
+            (&*entities, &transforms, &selecteds, !&marked)
+                .join()
+                .map(|(e, transform, _, _)| (e, transform.clone()))
+                .collect();
         // Mark selected entities
-        if let Some((e, t)) = marker_transform {
+        for (e, t) in newly_selected {
             info!("Found selected element!");
             let marker_entity = entities
                 .build_entity()
@@ -121,6 +374,233 @@ impl<'s> System<'s> for MarkSelectedSystem {
     }
 }
 
+/// Tracks an in-progress rubber-band (marquee) drag selection and the
+/// translucent UI entity used to render it.
+#[derive(Default)]
+struct DragSelect {
+    anchor: Option<(f32, f32)>,
+    marquee_entity: Option<Entity>,
+}
+
+/// Side length, in world units, of a `SpatialIndex` bucket.
+const SPATIAL_CELL_SIZE: f32 = 64.0;
+
+/// A spatial hash of `SomeObject` entities by world position, rebuilt every
+/// frame, so click-picking and neighbor queries only scan nearby buckets
+/// instead of every entity.
+struct SpatialIndex {
+    cell_size: f32,
+    buckets: HashMap<(i32, i32), Vec<Entity>>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        SpatialIndex {
+            cell_size: SPATIAL_CELL_SIZE,
+            buckets: HashMap::new(),
+        }
+    }
+}
+
+impl SpatialIndex {
+    fn bucket_of(&self, pos: (f32, f32)) -> (i32, i32) {
+        (
+            (pos.0 / self.cell_size).floor() as i32,
+            (pos.1 / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn rebuild(&mut self, entities_with_pos: impl Iterator<Item = (Entity, (f32, f32))>) {
+        self.buckets.clear();
+        for (entity, pos) in entities_with_pos {
+            self.buckets
+                .entry(self.bucket_of(pos))
+                .or_insert_with(Vec::new)
+                .push(entity);
+        }
+    }
+
+    /// Invokes `visit` once for every entity in a bucket overlapping the
+    /// circle of `radius` around `pos`.
+    fn for_each_in_radius(&self, pos: (f32, f32), radius: f32, mut visit: impl FnMut(Entity)) {
+        let (cell_x, cell_y) = self.bucket_of(pos);
+        let span = (radius / self.cell_size).ceil() as i32;
+        for dx in -span..=span {
+            for dy in -span..=span {
+                if let Some(bucket) = self.buckets.get(&(cell_x + dx, cell_y + dy)) {
+                    for &entity in bucket {
+                        visit(entity);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod spatial_index_tests {
+    use super::*;
+
+    fn dummy_entities(world: &mut World, n: usize) -> Vec<Entity> {
+        (0..n).map(|_| world.create_entity().build()).collect()
+    }
+
+    #[test]
+    fn only_visits_entities_in_buckets_near_the_query() {
+        let mut world = World::new();
+        let entities = dummy_entities(&mut world, 3);
+
+        let mut index = SpatialIndex::default();
+        index.rebuild(
+            vec![
+                (entities[0], (0., 0.)),
+                (entities[1], (10., 0.)),
+                (entities[2], (500., 500.)),
+            ]
+            .into_iter(),
+        );
+
+        let mut hits = Vec::new();
+        index.for_each_in_radius((0., 0.), 20., |e| hits.push(e));
+
+        assert!(hits.contains(&entities[0]));
+        assert!(hits.contains(&entities[1]));
+        assert!(!hits.contains(&entities[2]));
+    }
+
+    #[test]
+    fn rebuild_clears_stale_entries() {
+        let mut world = World::new();
+        let entities = dummy_entities(&mut world, 1);
+
+        let mut index = SpatialIndex::default();
+        index.rebuild(vec![(entities[0], (0., 0.))].into_iter());
+        index.rebuild(std::iter::empty());
+
+        let mut hits = Vec::new();
+        index.for_each_in_radius((0., 0.), 1000., |e| hits.push(e));
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn bucket_of_groups_positions_into_the_same_cell() {
+        let index = SpatialIndex::default();
+        assert_eq!(index.bucket_of((0., 0.)), index.bucket_of((1., 1.)));
+        assert_ne!(
+            index.bucket_of((0., 0.)),
+            index.bucket_of((SPATIAL_CELL_SIZE, 0.))
+        );
+    }
+}
+
+/// Tracks whether a mouse button is currently held, and whether it changed
+/// state (`pressed`/`released`) on this frame, so callers can react to
+/// click edges instead of the continuously-held state `InputHandler`
+/// reports.
+#[derive(Default)]
+struct MouseButtonState {
+    down: bool,
+    pressed: bool,
+    released: bool,
+}
+
+impl MouseButtonState {
+    fn update(&mut self, now_down: bool) {
+        self.pressed = now_down && !self.down;
+        self.released = !now_down && self.down;
+        self.down = now_down;
+    }
+}
+
+/// Edge-triggered left/right/move mouse button state, refreshed once per frame.
+#[derive(Default)]
+struct MouseInput {
+    left: MouseButtonState,
+    right: MouseButtonState,
+    mv: MouseButtonState,
+}
+
+struct MouseInputSystem;
+
+impl<'s> System<'s> for MouseInputSystem {
+    type SystemData = (Read<'s, InputHandler<StringBindings>>, Write<'s, MouseInput>);
+
+    fn run(&mut self, (input, mut mouse_input): Self::SystemData) {
+        mouse_input
+            .left
+            .update(input.action_is_down("left_click").unwrap_or(false));
+        mouse_input
+            .right
+            .update(input.action_is_down("right_click").unwrap_or(false));
+        mouse_input
+            .mv
+            .update(input.action_is_down("move").unwrap_or(false));
+    }
+}
+
+/// Sprite used for freshly-spawned `SomeObject` entities.
+struct ObjectSpriteRender {
+    sprite_render: SpriteRender,
+}
+
+/// Distance, in world units, within which a click is considered to hit an
+/// existing `SomeObject`.
+const CLICK_PICK_RADIUS: f32 = 32.0;
+
+struct SpatialIndexSystem;
+
+impl<'s> System<'s> for SpatialIndexSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, SomeObject>,
+        Write<'s, SpatialIndex>,
+    );
+
+    fn run(&mut self, (entities, transforms, some_objects, mut spatial_index): Self::SystemData) {
+        spatial_index.rebuild(
+            (&*entities, &transforms, &some_objects)
+                .join()
+                .map(|(e, transform, _)| (e, (transform.translation().x, transform.translation().y))),
+        );
+    }
+}
+
+/// Converts the current mouse position into hidpi-corrected world
+/// coordinates, matching the mapping used for movement orders. `camera_offset`
+/// is the current camera translation, since the screen center no longer maps
+/// to world origin once the camera has panned.
+fn mouse_world_position(
+    input: &InputHandler<StringBindings>,
+    screen_dimension: &ScreenDimensions,
+    camera_offset: (f32, f32),
+) -> Option<(f32, f32)> {
+    let hidpi_factor = screen_dimension.hidpi_factor() as f32;
+    let (screen_size_x, screen_size_y) = (
+        screen_dimension.width() / hidpi_factor,
+        screen_dimension.height() / hidpi_factor,
+    );
+    input.mouse_position().map(|(x, y)| {
+        (
+            (x / hidpi_factor) - (screen_size_x / 2.) + camera_offset.0,
+            -(y / hidpi_factor) + (screen_size_y / 2.) + camera_offset.1,
+        )
+    })
+}
+
+/// Distance, in world units, at which a waypoint is considered reached.
+const WAYPOINT_ARRIVAL_RADIUS: f32 = 2.0;
+
+/// Radius, in world units, within which moving objects repel one another.
+const SEPARATION_RADIUS: f32 = 48.0;
+
+/// How strongly the separation vector is blended against goal-seeking.
+const SEPARATION_WEIGHT: f32 = 24.0;
+
+/// Separation magnitude above which a unit arriving at its final waypoint
+/// is considered crowded and stops rather than pushing further in.
+const CROWDED_THRESHOLD: f32 = 0.6;
+
 struct MouseSystem;
 
 impl<'s> System<'s> for MouseSystem {
@@ -129,10 +609,20 @@ impl<'s> System<'s> for MouseSystem {
         Read<'s, InputHandler<StringBindings>>,
         WriteStorage<'s, Transform>,
         WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiImage>,
         WriteStorage<'s, Selected>,
         WriteStorage<'s, SomeObject>,
         ReadExpect<'s, ScreenDimensions>,
         WriteStorage<'s, MarkedAsSelected>,
+        Write<'s, DragSelect>,
+        ReadExpect<'s, NavGrid>,
+        ReadExpect<'s, SpatialIndex>,
+        Read<'s, MouseInput>,
+        ReadExpect<'s, ObjectSpriteRender>,
+        WriteStorage<'s, SpriteRender>,
+        WriteStorage<'s, Selectable<()>>,
+        WriteStorage<'s, Interactable>,
+        ReadStorage<'s, Camera>,
     );
 
     fn run(
@@ -142,61 +632,295 @@ impl<'s> System<'s> for MouseSystem {
             input,
             mut transforms,
             mut ui_transforms,
-            selected,
+            mut ui_images,
+            mut selected,
             mut some_objects,
             screen_dimension,
-            marked_as_selected,
+            mut marked_as_selected,
+            mut drag_select,
+            nav_grid,
+            spatial_index,
+            mouse_input,
+            object_sprite_render,
+            mut sprite_renders,
+            mut selectables,
+            mut interactables,
+            cameras,
         ): Self::SystemData,
     ) {
-        // Compute point where selected object ist ordered to
-        for (transform, _, mut some_object) in (&transforms, &selected, &mut some_objects).join() {
-            if let Some(pressed) = input.action_is_down("move") {
-                if pressed {
-                    let hidpi_factor = screen_dimension.hidpi_factor() as f32;
-                    let (screen_size_x, screen_size_y) = (
-                        screen_dimension.width() / hidpi_factor,
-                        screen_dimension.height() / hidpi_factor,
-                    );
-                    some_object.ordered_to = match input.mouse_position() {
-                        Some((x, y)) => Some((
-                            (x / hidpi_factor) - (screen_size_x / 2.),
-                            -(y / hidpi_factor) + (screen_size_y / 2.),
-                        )),
-                        None => None,
-                    };
-                    info!(
-                        "Ordered object to move to position {:?} current transform position {:?}",
-                        some_object.ordered_to,
-                        transform.translation()
-                    );
+        // The camera can now pan (edge-scroll/WASD/focus), so the screen
+        // center no longer maps to world origin; read its translation once
+        // up front and thread it through every mouse_world_position() call
+        let camera_offset = (&transforms, &cameras)
+            .join()
+            .map(|(transform, _)| (transform.translation().x, transform.translation().y))
+            .next()
+            .unwrap_or((0., 0.));
+
+        // Edge-triggered click spawn/despawn, guarded against the held-down
+        // state `action_is_down` would otherwise report every frame
+        if mouse_input.left.pressed {
+            if let Some((x, y)) = mouse_world_position(&input, &screen_dimension, camera_offset) {
+                let mut occupied = false;
+                spatial_index.for_each_in_radius((x, y), CLICK_PICK_RADIUS, |e| {
+                    if let Some(transform) = transforms.get(e) {
+                        let (ox, oy) = (transform.translation().x, transform.translation().y);
+                        if (ox - x).powi(2) + (oy - y).powi(2) <= CLICK_PICK_RADIUS.powi(2) {
+                            occupied = true;
+                        }
+                    }
+                });
+
+                if !occupied {
+                    let new_entity = entities.create();
+                    let mut spawn_transform = Transform::default();
+                    spawn_transform.set_translation_xyz(x, y, 0.);
+                    transforms
+                        .insert(new_entity, spawn_transform)
+                        .expect("Unable to insert transform for spawned entity");
+                    ui_transforms
+                        .insert(
+                            new_entity,
+                            UiTransform::new(
+                                format!("object_{}", new_entity.id()),
+                                Anchor::Middle,
+                                Anchor::Middle,
+                                x,
+                                y,
+                                0.,
+                                64.,
+                                64.,
+                            ),
+                        )
+                        .expect("Unable to insert UiTransform for spawned entity");
+                    sprite_renders
+                        .insert(new_entity, object_sprite_render.sprite_render.clone())
+                        .expect("Unable to insert SpriteRender for spawned entity");
+                    selectables
+                        .insert(new_entity, Selectable::<()>::new(0))
+                        .expect("Unable to insert Selectable for spawned entity");
+                    interactables
+                        .insert(new_entity, Interactable)
+                        .expect("Unable to insert Interactable for spawned entity");
+                    some_objects
+                        .insert(new_entity, SomeObject::new())
+                        .expect("Unable to insert SomeObject for spawned entity");
+                    info!("Spawned new object at {:?}", (x, y));
                 }
             }
         }
 
-        // Move transform and UiTransform if object is ordered to move
-        for (transform, mut ui_transform, some_object) in
-            (&mut transforms, &mut ui_transforms, &some_objects).join()
+        if mouse_input.right.pressed {
+            if let Some((x, y)) = mouse_world_position(&input, &screen_dimension, camera_offset) {
+                let mut target = None;
+                spatial_index.for_each_in_radius((x, y), CLICK_PICK_RADIUS, |e| {
+                    if target.is_some() {
+                        return;
+                    }
+                    if let Some(transform) = transforms.get(e) {
+                        let (ox, oy) = (transform.translation().x, transform.translation().y);
+                        if (ox - x).powi(2) + (oy - y).powi(2) <= CLICK_PICK_RADIUS.powi(2) {
+                            target = Some(e);
+                        }
+                    }
+                });
+
+                if let Some(target_entity) = target {
+                    if let Some(marked) = marked_as_selected.remove(target_entity) {
+                        let marker_entity = entities.entity(marked.index);
+                        if entities.is_alive(marker_entity) {
+                            entities
+                                .delete(marker_entity)
+                                .expect("Unable to delete marker entity");
+                        }
+                    }
+                    entities
+                        .delete(target_entity)
+                        .expect("Unable to delete object entity");
+                    info!("Despawned object at {:?}", (x, y));
+                }
+            }
+        }
+        // Compute point where selected object ist ordered to, and plan an
+        // A* path to it over the navigation grid. Only (re)plan on the frame
+        // the move action is pressed, not every frame it's held, so dragging
+        // a unit doesn't re-run A* for every selected unit every frame.
+        if mouse_input.mv.pressed {
+            let target = mouse_world_position(&input, &screen_dimension, camera_offset);
+            for (transform, _, mut some_object) in
+                (&transforms, &selected, &mut some_objects).join()
             {
-                if let Some((target_pos_x, target_pos_y)) = some_object.ordered_to {
-                    let movement_vec = (
-                        target_pos_x - transform.translation().x,
-                        target_pos_y - transform.translation().y,
-                    );
-                    let movement_length = 5. * (movement_vec.0.powi(2) + movement_vec.1.powi(2)).sqrt();
-                    transform.append_translation_xyz(
-                        movement_vec.0 / movement_length,
-                        movement_vec.1 / movement_length,
-                        0.,
-                    );
+                some_object.ordered_to = target;
+                let current = (transform.translation().x, transform.translation().y);
+                some_object.path = target
+                    .and_then(|goal| nav_grid.find_path(current, goal))
+                    .unwrap_or_default();
+                info!(
+                    "Ordered object to move to position {:?} current transform position {:?}",
+                    some_object.ordered_to,
+                    transform.translation()
+                );
+            }
+        }
+
+        // Rubber-band (marquee) multi-selection
+        if let Some(select_down) = input.action_is_down("select") {
+            let world_pos = mouse_world_position(&input, &screen_dimension, camera_offset);
+            if select_down {
+                match (drag_select.anchor, drag_select.marquee_entity) {
+                    (None, _) => {
+                        drag_select.anchor = world_pos;
+                        if let Some((x, y)) = world_pos {
+                            let marquee_transform = UiTransform::new(
+                                "marquee_selection".to_string(),
+                                Anchor::Middle,
+                                Anchor::Middle,
+                                x,
+                                y,
+                                10.,
+                                0.,
+                                0.,
+                            );
+                            let marquee_entity = entities
+                                .build_entity()
+                                .with(marquee_transform, &mut ui_transforms)
+                                .with(UiImage::SolidColor([0.3, 0.7, 1.0, 0.25]), &mut ui_images)
+                                .build();
+                            drag_select.marquee_entity = Some(marquee_entity);
+                        }
+                    }
+                    (Some((anchor_x, anchor_y)), Some(marquee_entity)) => {
+                        if let Some((x, y)) = world_pos {
+                            if let Some(marquee_transform) = ui_transforms.get_mut(marquee_entity) {
+                                let (min_x, max_x) = (anchor_x.min(x), anchor_x.max(x));
+                                let (min_y, max_y) = (anchor_y.min(y), anchor_y.max(y));
+                                marquee_transform.local_x = (min_x + max_x) / 2.;
+                                marquee_transform.local_y = (min_y + max_y) / 2.;
+                                marquee_transform.width = max_x - min_x;
+                                marquee_transform.height = max_y - min_y;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            } else if let Some((anchor_x, anchor_y)) = drag_select.anchor.take() {
+                if let Some((x, y)) = world_pos {
+                    let (min_x, max_x) = (anchor_x.min(x), anchor_x.max(x));
+                    let (min_y, max_y) = (anchor_y.min(y), anchor_y.max(y));
+                    let shift_held = input.action_is_down("shift").unwrap_or(false);
+
+                    // Only scan the buckets overlapping the marquee's bounding
+                    // circle instead of joining over every transform
+                    let center = ((min_x + max_x) / 2., (min_y + max_y) / 2.);
+                    let radius = ((max_x - min_x).powi(2) + (max_y - min_y).powi(2)).sqrt() / 2.;
+                    let mut hits = Vec::new();
+                    spatial_index.for_each_in_radius(center, radius, |e| {
+                        if let Some(transform) = transforms.get(e) {
+                            let (x, y) = (transform.translation().x, transform.translation().y);
+                            if x >= min_x && x <= max_x && y >= min_y && y <= max_y {
+                                hits.push(e);
+                            }
+                        }
+                    });
+
+                    if !shift_held {
+                        let to_clear: Vec<Entity> = (&*entities, &selected)
+                            .join()
+                            .map(|(e, _)| e)
+                            .filter(|e| !hits.contains(e))
+                            .collect();
+                        for e in to_clear {
+                            selected.remove(e);
+                        }
+                    }
+                    for e in hits {
+                        selected.insert(e, Selected).expect("Unable to select entity");
+                    }
+                }
+                if let Some(marquee_entity) = drag_select.marquee_entity.take() {
+                    if entities.is_alive(marquee_entity) {
+                        entities
+                            .delete(marquee_entity)
+                            .expect("Unable to delete marquee entity");
+                    }
+                }
+            }
+        }
+
+        // Snapshot positions up front: the steering loop below needs read
+        // access to neighbor transforms while holding a mutable borrow of
+        // its own transform, which WriteStorage can't do in one join
+        let positions: HashMap<Entity, (f32, f32)> = (&*entities, &transforms)
+            .join()
+            .map(|(e, t)| (e, (t.translation().x, t.translation().y)))
+            .collect();
+
+        // Steer transform and UiTransform toward the head of the A*-planned
+        // waypoint queue, blended with a boids-style separation vector so
+        // units ordered to the same point don't fully overlap
+        for (e, transform, mut ui_transform, mut some_object) in
+            (&*entities, &mut transforms, &mut ui_transforms, &mut some_objects).join()
+        {
+            let target = match some_object.path.first() {
+                Some(&target) => target,
+                None => {
+                    some_object.ordered_to = None;
+                    continue;
+                }
+            };
+
+            let current = (transform.translation().x, transform.translation().y);
+            let goal_vec = (target.0 - current.0, target.1 - current.1);
+            let goal_dist = (goal_vec.0.powi(2) + goal_vec.1.powi(2)).sqrt();
+
+            if goal_dist <= WAYPOINT_ARRIVAL_RADIUS {
+                some_object.path.remove(0);
+                continue;
+            }
 
-                    ui_transform.local_x += (movement_vec.0 / movement_length) as f32;
-                    ui_transform.local_y += (movement_vec.1 / movement_length) as f32;
+            let mut separation = (0., 0.);
+            spatial_index.for_each_in_radius(current, SEPARATION_RADIUS, |other| {
+                if other == e {
+                    return;
                 }
+                if let Some(&(other_x, other_y)) = positions.get(&other) {
+                    let away = (current.0 - other_x, current.1 - other_y);
+                    let distance = (away.0.powi(2) + away.1.powi(2)).sqrt().max(0.01);
+                    if distance < SEPARATION_RADIUS {
+                        separation.0 += away.0 / (distance * distance);
+                        separation.1 += away.1 / (distance * distance);
+                    }
+                }
+            });
+
+            // Arrival check: stop at the formation's edge instead of
+            // jittering in place if the final waypoint is already crowded
+            let separation_len = (separation.0.powi(2) + separation.1.powi(2)).sqrt();
+            if some_object.path.len() == 1 && separation_len > CROWDED_THRESHOLD {
+                continue;
+            }
+
+            let goal_seek = (goal_vec.0 / goal_dist, goal_vec.1 / goal_dist);
+            let blended = (
+                goal_seek.0 + separation.0 * SEPARATION_WEIGHT,
+                goal_seek.1 + separation.1 * SEPARATION_WEIGHT,
+            );
+            let blended_len = (blended.0.powi(2) + blended.1.powi(2)).sqrt();
+            if blended_len > 0. && some_object.speed > 0. {
+                let step = some_object.speed / blended_len;
+                transform.append_translation_xyz(blended.0 * step, blended.1 * step, 0.);
+                ui_transform.local_x += (blended.0 * step) as f32;
+                ui_transform.local_y += (blended.1 * step) as f32;
             }
+        }
 
-        // Move marker for selected entities transform
-        for (some_object, marked) in (&some_objects, &marked_as_selected).join() {
-            if let Some((target_pos_x, target_pos_y)) = some_object.ordered_to {
+        // Move marker for selected entities transform, following its
+        // owner's final blended position each frame
+        for (owner, _, marked) in (&*entities, &some_objects, &marked_as_selected).join() {
+            let owner_pos = transforms
+                .get(owner)
+                .map(|t| (t.translation().x, t.translation().y));
+            if let Some((target_pos_x, target_pos_y)) = owner_pos {
                 let marker_entity = entities.entity(marked.index);
                 if entities.is_alive(marker_entity) {
                     let marker_transform = transforms
@@ -208,16 +932,154 @@ impl<'s> System<'s> for MouseSystem {
                         target_pos_x - marker_transform.translation().x,
                         target_pos_y - marker_transform.translation().y,
                     );
+                    let distance = (movement_vec.0.powi(2) + movement_vec.1.powi(2)).sqrt();
+
+                    // The marker starts each selection cloned onto the
+                    // owner's exact transform, so distance is exactly 0 on
+                    // the very next frame if the owner hasn't moved yet;
+                    // skip the divide instead of producing NaN
+                    if distance > WAYPOINT_ARRIVAL_RADIUS {
+                        let movement_length = distance;
+                        marker_transform.append_translation_xyz(
+                            movement_vec.0 / movement_length,
+                            movement_vec.1 / movement_length,
+                            0.,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Margin, in screen pixels, within which the cursor triggers edge-scroll.
+const CAMERA_EDGE_SCROLL_MARGIN: f32 = 24.0;
 
-                    let movement_length =
-                        5. * (movement_vec.0.powi(2) + movement_vec.1.powi(2)).sqrt();
-                    marker_transform.append_translation_xyz(
-                        movement_vec.0 / movement_length,
-                        movement_vec.1 / movement_length,
-                        0.,
+/// Camera pan speed, in world units per frame.
+const CAMERA_PAN_SPEED: f32 = 6.0;
+
+/// Ease factor applied each frame when lerping the camera toward the
+/// centroid of the selection on a "focus" action.
+const CAMERA_FOCUS_EASE: f32 = 0.08;
+
+/// World-space rectangle the camera's translation is clamped to.
+struct CameraBounds {
+    min: (f32, f32),
+    max: (f32, f32),
+}
+
+impl Default for CameraBounds {
+    fn default() -> Self {
+        CameraBounds {
+            min: (-512., -512.),
+            max: (512., 512.),
+        }
+    }
+}
+
+/// Pans the camera on edge-scroll and WASD/arrow input, clamps it to
+/// `CameraBounds`, and on a "focus" action eases it toward the centroid of
+/// the currently `Selected` objects so commanded units stay in view.
+struct CameraControlSystem;
+
+impl<'s> System<'s> for CameraControlSystem {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, InputHandler<StringBindings>>,
+        ReadExpect<'s, ScreenDimensions>,
+        ReadExpect<'s, CameraBounds>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Selected>,
+        WriteStorage<'s, Transform>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, input, screen_dimension, camera_bounds, cameras, selecteds, mut transforms): Self::SystemData,
+    ) {
+        let camera_entity = match (&*entities, &cameras).join().map(|(e, _)| e).next() {
+            Some(e) => e,
+            None => return,
+        };
+
+        // Snapshot the selection centroid before taking a mutable borrow of
+        // the camera's own transform below
+        let focus_centroid = {
+            let mut sum = (0., 0.);
+            let mut count = 0;
+            for (transform, _) in (&transforms, &selecteds).join() {
+                sum.0 += transform.translation().x;
+                sum.1 += transform.translation().y;
+                count += 1;
+            }
+            if count > 0 {
+                Some((sum.0 / count as f32, sum.1 / count as f32))
+            } else {
+                None
+            }
+        };
+
+        let mut pan = (0., 0.);
+        if let Some((x, y)) = input.mouse_position() {
+            let hidpi_factor = screen_dimension.hidpi_factor() as f32;
+            let (x, y) = (x / hidpi_factor, y / hidpi_factor);
+            let (screen_w, screen_h) = (
+                screen_dimension.width() / hidpi_factor,
+                screen_dimension.height() / hidpi_factor,
+            );
+            if x <= CAMERA_EDGE_SCROLL_MARGIN {
+                pan.0 -= CAMERA_PAN_SPEED;
+            } else if x >= screen_w - CAMERA_EDGE_SCROLL_MARGIN {
+                pan.0 += CAMERA_PAN_SPEED;
+            }
+            if y <= CAMERA_EDGE_SCROLL_MARGIN {
+                pan.1 += CAMERA_PAN_SPEED;
+            } else if y >= screen_h - CAMERA_EDGE_SCROLL_MARGIN {
+                pan.1 -= CAMERA_PAN_SPEED;
+            }
+        }
+
+        if input.action_is_down("camera_left").unwrap_or(false) {
+            pan.0 -= CAMERA_PAN_SPEED;
+        }
+        if input.action_is_down("camera_right").unwrap_or(false) {
+            pan.0 += CAMERA_PAN_SPEED;
+        }
+        if input.action_is_down("camera_up").unwrap_or(false) {
+            pan.1 += CAMERA_PAN_SPEED;
+        }
+        if input.action_is_down("camera_down").unwrap_or(false) {
+            pan.1 -= CAMERA_PAN_SPEED;
+        }
+
+        let focus = input.action_is_down("focus").unwrap_or(false);
+
+        if let Some(camera_transform) = transforms.get_mut(camera_entity) {
+            camera_transform.append_translation_xyz(pan.0, pan.1, 0.);
+
+            if focus {
+                if let Some((target_x, target_y)) = focus_centroid {
+                    let current = (
+                        camera_transform.translation().x,
+                        camera_transform.translation().y,
                     );
+                    let eased_x = current.0 + (target_x - current.0) * CAMERA_FOCUS_EASE;
+                    let eased_y = current.1 + (target_y - current.1) * CAMERA_FOCUS_EASE;
+                    let z = camera_transform.translation().z;
+                    camera_transform.set_translation_xyz(eased_x, eased_y, z);
                 }
             }
+
+            let z = camera_transform.translation().z;
+            let clamped_x = camera_transform
+                .translation()
+                .x
+                .clamp(camera_bounds.min.0, camera_bounds.max.0);
+            let clamped_y = camera_transform
+                .translation()
+                .y
+                .clamp(camera_bounds.min.1, camera_bounds.max.1);
+            camera_transform.set_translation_xyz(clamped_x, clamped_y, z);
         }
     }
 }
@@ -244,7 +1106,9 @@ fn load_sprite_sheet(world: &mut World) -> Handle<SpriteSheet> {
     )
 }
 
-struct Example;
+struct Example {
+    scene: ScriptedScene,
+}
 
 impl SimpleState for Example {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
@@ -263,73 +1127,60 @@ impl SimpleState for Example {
             .with(Camera::standard_2d(width, height))
             .build();
 
-        let sprite_sheet_handle = load_sprite_sheet(world);
-
-        // Initialize left object
-        let mut left_transform = Transform::default();
-        left_transform.set_translation_xyz(0.0, 0.0, 0.0);
-
-        let sprite_render = SpriteRender {
-            sprite_sheet: sprite_sheet_handle.clone(),
-            sprite_number: 0,
-        };
+        world.add_resource(CameraBounds::default());
 
-        let ui_transform = UiTransform::new(
-            "test".to_string(),
-            Anchor::Middle,
-            Anchor::Middle,
-            0.,
-            0.,
-            0.,
-            64.,
-            64.,
-        );
+        let sprite_sheet_handle = load_sprite_sheet(world);
 
-        world
-            .create_entity()
-            .with(sprite_render.clone())
-            .with(left_transform)
-            .with(ui_transform)
-            .with(Selectable::<()>::new(0))
-            .with(Interactable)
-            .with(SomeObject::new())
-            .build();
+        // A 64x64 cell grid centered on the origin, covering a 1024x1024
+        // play area with no obstacles blocked yet
+        world.add_resource(NavGrid::new(64, 64, (-512., -512.)));
 
-        // Initialize right object
-        let mut right_transform = Transform::default();
-        right_transform.set_translation_xyz(100.0, 100.0, 0.0);
+        // Spawn every object described by the scene script
+        for (index, spawn) in self.scene.spawns.iter().enumerate() {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(spawn.x, spawn.y, 0.0);
 
-        let right_sprite_render = SpriteRender {
-            sprite_sheet: sprite_sheet_handle.clone(),
-            sprite_number: 1,
-        };
+            let sprite_render = SpriteRender {
+                sprite_sheet: sprite_sheet_handle.clone(),
+                sprite_number: spawn.sprite_number,
+            };
 
-        let right_ui_transform = UiTransform::new(
-            "test2".to_string(),
-            Anchor::Middle,
-            Anchor::Middle,
-            100.,
-            100.,
-            0.,
-            64.,
-            64.,
-        );
+            let ui_transform = UiTransform::new(
+                format!("scripted_object_{}", index),
+                Anchor::Middle,
+                Anchor::Middle,
+                spawn.x,
+                spawn.y,
+                0.,
+                64.,
+                64.,
+            );
 
-        world
-            .create_entity()
-            .with(right_sprite_render.clone())
-            .with(right_transform)
-            .with(right_ui_transform)
-            .with(Selectable::<()>::new(0))
-            .with(Interactable)
-            .with(SomeObject::new())
-            .build();
+            world
+                .create_entity()
+                .with(sprite_render)
+                .with(transform)
+                .with(ui_transform)
+                .with(Selectable::<()>::new(0))
+                .with(Interactable)
+                .with(SomeObject::with_speed(spawn.speed))
+                .build();
+        }
 
-        // Initialize selected frame as resource
+        // Initialize selected frame as resource, using the marker sprite
+        // index configured by the scene script
         world.add_resource(SelectedSpriteRender {
             sprite_render: SpriteRender {
                 sprite_sheet: sprite_sheet_handle.clone(),
-                sprite_number: 2,
+                sprite_number: self.scene.config.marker_sprite_number,
+            },
+        });
+
+        // Sprite used for objects spawned dynamically by left-click
+        world.add_resource(ObjectSpriteRender {
+            sprite_render: SpriteRender {
+                sprite_sheet: sprite_sheet_handle.clone(),
+                sprite_number: 0,
             },
         });
     }
@@ -348,6 +1199,11 @@ fn main() -> amethyst::Result<()> {
 
     let bindings_config_path = app_root.join("src/resources/bindings_config.ron");
 
+    let scene_path = app_root.join("src/resources/scene.rhai");
+    let scripted_scene =
+        scene_script::load_scene(&scene_path).expect("Unable to load scene script");
+    let clear_color = scripted_scene.config.clear_color;
+
     let resources = app_root.join("src/assets/");
     let game_data = GameDataBuilder::default()
         //.with_bundle(WindowBundle::from_config_path(display_config_path))?
@@ -356,20 +1212,36 @@ fn main() -> amethyst::Result<()> {
         )?
         .with_bundle(TransformBundle::new())?
         .with_bundle(UiBundle::<StringBindings>::new())?
-        .with(MouseSystem, "mouse_system", &["input_system"])
+        .with(SpatialIndexSystem, "spatial_index_system", &[])
+        .with(MouseInputSystem, "mouse_input_system", &["input_system"])
+        .with(
+            CameraControlSystem,
+            "camera_control_system",
+            &["input_system"],
+        )
+        .with(
+            MouseSystem,
+            "mouse_system",
+            &["input_system", "spatial_index_system", "mouse_input_system"],
+        )
         .with(MarkSelectedSystem, "mark_selected_system", &[])
         .with_bundle(
             RenderingBundle::<DefaultBackend>::new()
                 // The RenderToWindow plugin provides all the scaffolding for opening a window and
                 // drawing on it
                 .with_plugin(
-                    RenderToWindow::from_config_path(display_config_path)
-                        .with_clear([0.34, 0.36, 0.52, 1.0]),
+                    RenderToWindow::from_config_path(display_config_path).with_clear(clear_color),
                 )
                 .with_plugin(RenderFlat2D::default())
                 .with_plugin(RenderUi::default()),
         )?;
-    let mut game = Application::new(resources, Example, game_data)?;
+    let mut game = Application::new(
+        resources,
+        Example {
+            scene: scripted_scene,
+        },
+        game_data,
+    )?;
     game.run();
     Ok(())
 }
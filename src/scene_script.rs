@@ -0,0 +1,84 @@
+//! Loads `.rhai` scene files describing which objects to spawn and a few
+//! scene-wide settings, so designers can edit layouts and per-unit speed
+//! without recompiling.
+
+use rhai::{Engine, EvalAltResult};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// One object to spawn, as built by a `spawn_object(sprite, x, y, speed)`
+/// call in the scene script.
+#[derive(Debug, Clone)]
+pub struct ScriptedSpawn {
+    pub sprite_number: u32,
+    pub x: f32,
+    pub y: f32,
+    pub speed: f32,
+}
+
+/// Scene-wide settings, as built by a `config(marker_sprite, r, g, b, a)`
+/// call in the scene script.
+#[derive(Debug, Clone)]
+pub struct ScriptedSceneConfig {
+    pub marker_sprite_number: u32,
+    pub clear_color: [f32; 4],
+}
+
+impl Default for ScriptedSceneConfig {
+    fn default() -> Self {
+        ScriptedSceneConfig {
+            marker_sprite_number: 2,
+            clear_color: [0.34, 0.36, 0.52, 1.0],
+        }
+    }
+}
+
+/// The fully-resolved scene produced by running a `.rhai` scene file.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedScene {
+    pub config: ScriptedSceneConfig,
+    pub spawns: Vec<ScriptedSpawn>,
+}
+
+/// Runs the `.rhai` scene file at `path`, translating every `spawn_object`
+/// and `config` builder call it makes into a `ScriptedScene`.
+pub fn load_scene(path: &Path) -> Result<ScriptedScene, Box<EvalAltResult>> {
+    let scene = Rc::new(RefCell::new(ScriptedScene::default()));
+    let mut engine = Engine::new();
+
+    let spawns = Rc::clone(&scene);
+    engine.register_fn(
+        "spawn_object",
+        move |sprite: i64, x: f64, y: f64, speed: f64| {
+            spawns.borrow_mut().spawns.push(ScriptedSpawn {
+                sprite_number: sprite as u32,
+                x: x as f32,
+                y: y as f32,
+                speed: speed as f32,
+            });
+        },
+    );
+
+    let config = Rc::clone(&scene);
+    engine.register_fn(
+        "config",
+        move |marker_sprite: i64, r: f64, g: f64, b: f64, a: f64| {
+            config.borrow_mut().config = ScriptedSceneConfig {
+                marker_sprite_number: marker_sprite as u32,
+                clear_color: [r as f32, g as f32, b as f32, a as f32],
+            };
+        },
+    );
+
+    engine.consume_file(path.to_path_buf())?;
+
+    // Drop the engine first: it, not just this function, holds the other
+    // clones of `scene` via the registered closures, so `try_unwrap` would
+    // otherwise never succeed.
+    drop(engine);
+
+    Ok(Rc::try_unwrap(scene)
+        .expect("scene script builder closures outlived the run")
+        .into_inner())
+}